@@ -0,0 +1,248 @@
+use proc_macro2::TokenStream;
+
+/// Parameters accepted by `#[cynic(...)]` on a `#[derive(cynic::InlineFragments)]` enum.
+///
+/// Only `query_module` and `graphql_type` are read, both required; any other
+/// key (such as `schema_path` or `argument_struct`) is ignored, and the
+/// generated impl always uses `()` as its `Arguments`.  The enum maps onto a
+/// GraphQL union/interface rather than an object.
+struct InlineFragmentsDeriveInput {
+    query_module: syn::Path,
+    graphql_type: String,
+}
+
+pub fn inline_fragments_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    use quote::quote;
+    use syn::spanned::Spanned;
+
+    let input = parse_input(ast)?;
+    let ident = &ast.ident;
+    let query_module = &input.query_module;
+    let graphql_type = &input.graphql_type;
+    // `quote` splices a `String` as a string literal, which is only right for
+    // the `fn graphql_type() -> String` body below - the `TypeLock` position
+    // needs the type's bare identifier instead.
+    let type_lock = syn::Ident::new(graphql_type, proc_macro2::Span::call_site());
+
+    let data = match &ast.data {
+        syn::Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                ast.span(),
+                "cynic::InlineFragments can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut fragment_arms = Vec::with_capacity(data.variants.len());
+    let mut fallback_arm = None;
+
+    for variant in &data.variants {
+        if is_fallback_variant(variant) {
+            if fallback_arm.is_some() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "only one variant may be marked #[cynic(fallback)]",
+                ));
+            }
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "a variant marked #[cynic(fallback)] must be a unit variant, e.g. `Unknown`, since there's no matching fragment to decode it from",
+                ));
+            }
+            let variant_ident = &variant.ident;
+            fallback_arm = Some(quote! { Some(#ident::#variant_ident) });
+            continue;
+        }
+
+        let variant_ident = &variant.ident;
+        let inner_ty = variant_fragment_type(variant)?;
+
+        fragment_arms.push(quote! {
+            (
+                <#inner_ty as ::cynic::QueryFragment>::graphql_type(),
+                ::cynic::selection_set::map(
+                    #ident::#variant_ident,
+                    <#inner_ty as ::cynic::QueryFragment>::fragment(arguments.clone()),
+                ),
+            )
+        });
+    }
+
+    let fallback_arm = fallback_arm.unwrap_or(quote! { None });
+
+    Ok(quote! {
+        impl<'a> ::cynic::InlineFragments<'a> for #ident {
+            type TypeLock = #query_module::#type_lock;
+            type Arguments = ();
+
+            fn graphql_type() -> String {
+                #graphql_type.to_string()
+            }
+
+            fn fragments(
+                arguments: Self::Arguments,
+            ) -> Vec<(String, ::cynic::SelectionSet<'a, 'static, Self, Self::TypeLock>)> {
+                vec![#(#fragment_arms),*]
+            }
+
+            fn fallback() -> Option<Self> {
+                #fallback_arm
+            }
+        }
+    })
+}
+
+fn is_fallback_variant(variant: &syn::Variant) -> bool {
+    variant.attrs.iter().any(|attr| {
+        attr.path.is_ident("cynic")
+            && attr
+                .parse_args::<syn::Path>()
+                .map(|path| path.is_ident("fallback"))
+                .unwrap_or(false)
+    })
+}
+
+fn variant_fragment_type(variant: &syn::Variant) -> Result<&syn::Type, syn::Error> {
+    use syn::spanned::Spanned;
+
+    if let syn::Fields::Unnamed(fields) = &variant.fields {
+        if fields.unnamed.len() == 1 {
+            return Ok(&fields.unnamed.first().unwrap().ty);
+        }
+    }
+
+    Err(syn::Error::new(
+        variant.span(),
+        "each variant of an InlineFragments enum should wrap a single QueryFragment, e.g. `Human(Human)`",
+    ))
+}
+
+fn parse_input(ast: &syn::DeriveInput) -> Result<InlineFragmentsDeriveInput, syn::Error> {
+    use syn::spanned::Spanned;
+
+    let mut query_module = None;
+    let mut graphql_type = None;
+
+    for attr in &ast.attrs {
+        if !attr.path.is_ident("cynic") {
+            continue;
+        }
+
+        let meta = attr.parse_meta()?;
+        if let syn::Meta::List(list) = meta {
+            for nested in list.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = nested {
+                    if name_value.path.is_ident("query_module") {
+                        if let syn::Lit::Str(s) = &name_value.lit {
+                            query_module = Some(s.parse::<syn::Path>()?);
+                        }
+                    } else if name_value.path.is_ident("graphql_type") {
+                        if let syn::Lit::Str(s) = &name_value.lit {
+                            graphql_type = Some(s.value());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(InlineFragmentsDeriveInput {
+        query_module: query_module.ok_or_else(|| {
+            syn::Error::new(
+                ast.span(),
+                "cynic::InlineFragments requires a `query_module` attribute, e.g. #[cynic(query_module = \"query_dsl\")]",
+            )
+        })?,
+        graphql_type: graphql_type.ok_or_else(|| {
+            syn::Error::new(
+                ast.span(),
+                "cynic::InlineFragments requires a `graphql_type` attribute, e.g. #[cynic(graphql_type = \"SearchResult\")]",
+            )
+        })?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn derive(input: &str) -> Result<TokenStream, syn::Error> {
+        inline_fragments_derive(&syn::parse_str(input).unwrap())
+    }
+
+    #[test]
+    fn type_lock_is_an_identifier_not_a_string_literal() {
+        let output = derive(
+            r#"
+            #[cynic(query_module = "query_dsl", graphql_type = "SearchResult")]
+            enum Character {
+                Human(Human),
+                Droid(Droid),
+            }
+            "#,
+        )
+        .unwrap()
+        .to_string();
+        let flattened = output.replace(' ', "");
+
+        assert!(flattened.contains("typeTypeLock=query_dsl::SearchResult;"));
+        // The only string literal is the `graphql_type()` body.
+        assert_eq!(output.matches("\"SearchResult\"").count(), 1);
+        assert!(flattened.contains("fngraphql_type()->String{\"SearchResult\".to_string()}"));
+    }
+
+    #[test]
+    fn fallback_variant_must_be_unit() {
+        let err = derive(
+            r#"
+            #[cynic(query_module = "query_dsl", graphql_type = "SearchResult")]
+            enum Character {
+                Human(Human),
+                #[cynic(fallback)]
+                Unknown(Human),
+            }
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("must be a unit variant"));
+    }
+
+    #[test]
+    fn fallback_variant_is_threaded_into_fallback_fn() {
+        let output = derive(
+            r#"
+            #[cynic(query_module = "query_dsl", graphql_type = "SearchResult")]
+            enum Character {
+                Human(Human),
+                #[cynic(fallback)]
+                Unknown,
+            }
+            "#,
+        )
+        .unwrap()
+        .to_string();
+
+        let flattened = output.replace(' ', "");
+        assert!(flattened.contains("fnfallback()->Option<Self>{Some(Character::Unknown)}"));
+    }
+
+    #[test]
+    fn no_fallback_variant_defaults_to_none() {
+        let output = derive(
+            r#"
+            #[cynic(query_module = "query_dsl", graphql_type = "SearchResult")]
+            enum Character {
+                Human(Human),
+                Droid(Droid),
+            }
+            "#,
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(output.replace(' ', "").contains("fn fallback()->Option<Self>{None}".replace(' ', "").as_str()));
+    }
+}
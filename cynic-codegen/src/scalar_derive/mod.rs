@@ -0,0 +1,149 @@
+use proc_macro2::TokenStream;
+
+pub fn scalar_derive(ast: &syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    use quote::quote;
+
+    let ident = &ast.ident;
+    let inner_ty = newtype_inner_type(ast)?;
+    let graphql_type = graphql_type_attribute(ast)?.unwrap_or_else(|| ident.to_string());
+
+    Ok(quote! {
+        impl ::cynic::Scalar for #ident {
+            fn graphql_type() -> String {
+                #graphql_type.to_string()
+            }
+
+            fn decode(value: &serde_json::Value) -> Result<Self, ::cynic::DecodeError> {
+                serde_json::from_value::<#inner_ty>(value.clone())
+                    .map(#ident)
+                    .map_err(|e| ::cynic::DecodeError::Other(e.to_string()))
+            }
+
+            fn encode(&self) -> Result<serde_json::Value, ::cynic::SerializeError> {
+                serde_json::to_value(&self.0).map_err(|e| Box::new(e) as ::cynic::SerializeError)
+            }
+        }
+
+        impl From<#inner_ty> for #ident {
+            fn from(value: #inner_ty) -> Self {
+                #ident(value)
+            }
+        }
+
+        impl From<#ident> for #inner_ty {
+            fn from(value: #ident) -> Self {
+                value.0
+            }
+        }
+
+        impl ::cynic::SerializableArgument for #ident {
+            fn serialize(&self) -> Result<serde_json::Value, ::cynic::SerializeError> {
+                ::cynic::Scalar::encode(self)
+            }
+        }
+    })
+}
+
+/// Scalars are only supported on newtype structs - a tuple struct with a
+/// single field - since we need some inner type to delegate (de)serialization
+/// to.
+fn newtype_inner_type(ast: &syn::DeriveInput) -> Result<syn::Type, syn::Error> {
+    use syn::spanned::Spanned;
+
+    if let syn::Data::Struct(data) = &ast.data {
+        if let syn::Fields::Unnamed(fields) = &data.fields {
+            if fields.unnamed.len() == 1 {
+                return Ok(fields.unnamed.first().unwrap().ty.clone());
+            }
+        }
+    }
+
+    Err(syn::Error::new(
+        ast.span(),
+        "cynic::Scalar can only be derived for newtype structs, e.g. `struct MyId(String)`",
+    ))
+}
+
+/// Looks for a `#[cynic(graphql_type = "...")]` attribute, allowing the
+/// GraphQL scalar name to be overridden when it doesn't match the Rust type's
+/// own identifier - which is required as soon as more than one Rust scalar
+/// binds to the same GraphQL scalar (e.g. several ID newtypes all binding to
+/// `ID`).
+fn graphql_type_attribute(ast: &syn::DeriveInput) -> Result<Option<String>, syn::Error> {
+    for attr in &ast.attrs {
+        if !attr.path.is_ident("cynic") {
+            continue;
+        }
+
+        let meta = attr.parse_meta()?;
+        if let syn::Meta::List(list) = meta {
+            for nested in list.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = nested {
+                    if name_value.path.is_ident("graphql_type") {
+                        if let syn::Lit::Str(s) = name_value.lit {
+                            return Ok(Some(s.value()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn derive(input: &str) -> Result<TokenStream, syn::Error> {
+        scalar_derive(&syn::parse_str(input).unwrap())
+    }
+
+    #[test]
+    fn rejects_structs_with_more_than_one_field() {
+        let err = derive("struct VideogameId(pub u64, pub u64);").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("can only be derived for newtype structs"));
+    }
+
+    #[test]
+    fn rejects_unit_structs() {
+        let err = derive("struct VideogameId;").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("can only be derived for newtype structs"));
+    }
+
+    #[test]
+    fn graphql_type_attribute_overrides_the_default() {
+        let output = derive(r#"#[cynic(graphql_type = "ID")] struct VideogameId(pub u64);"#)
+            .unwrap()
+            .to_string();
+
+        assert!(output.replace(' ', "").contains("\"ID\".to_string()"));
+        assert!(!output.contains("\"VideogameId\""));
+    }
+
+    #[test]
+    fn no_attribute_defaults_to_the_struct_ident() {
+        let output = derive("struct VideogameId(pub u64);").unwrap().to_string();
+
+        assert!(output.replace(' ', "").contains("\"VideogameId\".to_string()"));
+    }
+
+    #[test]
+    fn generates_from_impls_between_the_struct_and_its_inner_type() {
+        let output = derive("struct VideogameId(pub u64);")
+            .unwrap()
+            .to_string()
+            .replace(' ', "");
+
+        assert!(output.contains("implFrom<u64>forVideogameId"));
+        assert!(output.contains("implFrom<VideogameId>foru64"));
+        assert!(output.contains("impl::cynic::SerializableArgumentforVideogameId"));
+    }
+}
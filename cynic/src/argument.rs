@@ -0,0 +1,109 @@
+use crate::SerializeError;
+
+/// A trait for values that can be serialized into a GraphQL argument value.
+///
+/// Implemented for the primitive GraphQL scalar types, for any
+/// `#[derive(cynic::Scalar)]` type (the derive generates the impl directly,
+/// rather than there being a blanket impl here, so it doesn't conflict with
+/// the generic `Option`/`Vec` impls below), and generically for `Option<T>`
+/// and `Vec<T>` of anything that implements it.
+pub trait SerializableArgument {
+    fn serialize(&self) -> Result<serde_json::Value, SerializeError>;
+}
+
+macro_rules! impl_serializable_argument {
+    ($ty:ty) => {
+        impl SerializableArgument for $ty {
+            fn serialize(&self) -> Result<serde_json::Value, SerializeError> {
+                Ok(serde_json::to_value(self)?)
+            }
+        }
+    };
+}
+
+impl_serializable_argument!(String);
+impl_serializable_argument!(i32);
+impl_serializable_argument!(f64);
+impl_serializable_argument!(bool);
+
+impl<T> SerializableArgument for Option<T>
+where
+    T: SerializableArgument,
+{
+    fn serialize(&self) -> Result<serde_json::Value, SerializeError> {
+        match self {
+            Some(value) => value.serialize(),
+            None => Ok(serde_json::Value::Null),
+        }
+    }
+}
+
+impl<T> SerializableArgument for Vec<T>
+where
+    T: SerializableArgument,
+{
+    fn serialize(&self) -> Result<serde_json::Value, SerializeError> {
+        self.iter()
+            .map(SerializableArgument::serialize)
+            .collect::<Result<Vec<_>, _>>()
+            .map(serde_json::Value::Array)
+    }
+}
+
+/// A named, already-serialized GraphQL argument attached to a query field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argument {
+    name: String,
+    value: serde_json::Value,
+}
+
+impl Argument {
+    pub fn new(name: &str, value: &dyn SerializableArgument) -> Result<Self, SerializeError> {
+        Ok(Argument {
+            name: name.to_string(),
+            value: value.serialize()?,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &serde_json::Value {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_primitives() {
+        assert_eq!("hello".to_string().serialize().unwrap(), serde_json::json!("hello"));
+        assert_eq!(1i32.serialize().unwrap(), serde_json::json!(1));
+        assert_eq!(true.serialize().unwrap(), serde_json::json!(true));
+    }
+
+    #[test]
+    fn serializes_option() {
+        assert_eq!(Some(1i32).serialize().unwrap(), serde_json::json!(1));
+        assert_eq!(None::<i32>.serialize().unwrap(), serde_json::Value::Null);
+    }
+
+    #[test]
+    fn serializes_vec() {
+        assert_eq!(
+            vec![1i32, 2, 3].serialize().unwrap(),
+            serde_json::json!([1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn argument_new_captures_name_and_serialized_value() {
+        let argument = Argument::new("first", &10i32).unwrap();
+
+        assert_eq!(argument.name(), "first");
+        assert_eq!(argument.value(), &serde_json::json!(10));
+    }
+}
@@ -0,0 +1,305 @@
+//! Support for the [Relay cursor connections spec][spec], so callers don't
+//! have to hand-write `edges { node { ... } } pageInfo { hasNextPage endCursor }`
+//! for every paginated field.
+//!
+//! [spec]: https://relay.dev/graphql/connections.htm
+//!
+//! Given a `SelectionSet` for a connection's node type, [`connection`] builds
+//! the full connection selection set (`edges`, `node`, `cursor` & `pageInfo`)
+//! and decodes it into a [`Connection<T>`].  [`ConnectionArguments`] implements
+//! `FragmentArguments` (plus the same `AsRef<()>`/`SubArguments<'a, ()>` bundle
+//! `#[derive(cynic::FragmentArguments)]` generates - see
+//! `cynic-codegen/src/fragment_arguments_derive`) so it can be used directly
+//! as (or folded into) the argument struct of a field returning a connection.
+//!
+//! To page through a connection, keep feeding `page_info.end_cursor` back in
+//! as `after` until `page_info.has_next_page` is `false`.
+
+use crate::selection_set::{self, SelectionSet};
+use crate::{Argument, FragmentArguments, SubArguments};
+
+/// The standard `first`/`after`/`last`/`before` arguments accepted by a Relay
+/// connection field.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectionArguments {
+    pub first: Option<i32>,
+    pub after: Option<String>,
+    pub last: Option<i32>,
+    pub before: Option<String>,
+}
+
+impl ConnectionArguments {
+    /// Builds the `first`/`after`/`last`/`before` GraphQL arguments for a
+    /// connection field from this struct's fields, skipping any that are
+    /// `None` rather than sending them through as GraphQL `null`s.
+    pub fn to_arguments(&self) -> Vec<Argument> {
+        let mut arguments = vec![];
+        if let Some(first) = self.first {
+            arguments.push(Argument::new("first", &first).unwrap());
+        }
+        if let Some(after) = &self.after {
+            arguments.push(Argument::new("after", after).unwrap());
+        }
+        if let Some(last) = self.last {
+            arguments.push(Argument::new("last", &last).unwrap());
+        }
+        if let Some(before) = &self.before {
+            arguments.push(Argument::new("before", before).unwrap());
+        }
+        arguments
+    }
+}
+
+impl FragmentArguments for ConnectionArguments {}
+
+// The same bundle `fragment_arguments_derive` generates for every other
+// `FragmentArguments` type, hand-written here since `ConnectionArguments`
+// isn't itself derived.
+impl AsRef<()> for ConnectionArguments {
+    fn as_ref(&self) -> &() {
+        &()
+    }
+}
+
+impl<'a> SubArguments<'a, ()> for ConnectionArguments {
+    fn from_arguments(&'a self) -> &'a () {
+        &()
+    }
+}
+
+/// The `pageInfo` block of a Relay connection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+/// A single edge of a connection - a `node` paired with its opaque `cursor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edge<T> {
+    pub cursor: String,
+    pub node: T,
+}
+
+/// A Relay connection - the `edges`/`pageInfo` wrapper GraphQL servers use to
+/// paginate a list field.
+///
+/// `nodes()` and `edges()` are always index-aligned with each other.  An
+/// empty `edges` list combined with `page_info.has_next_page == false`
+/// signals the end of pagination.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Connection<T> {
+    pub edges: Vec<Edge<T>>,
+    pub page_info: PageInfo,
+}
+
+impl<T> Connection<T> {
+    /// The nodes of this connection, in order, with their cursors discarded.
+    pub fn nodes(&self) -> impl Iterator<Item = &T> {
+        self.edges.iter().map(|edge| &edge.node)
+    }
+
+    /// The edges of this connection, in order.
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<T>> {
+        self.edges.iter()
+    }
+}
+
+/// Builds the `SelectionSet` for a Relay connection, given the selection set
+/// for a single node.
+///
+/// This is the building block behind `#[derive(cynic::QueryFragment)]` support
+/// for connection-typed fields - wrap a node fragment's `SelectionSet` with
+/// this to get the `edges { node { ... } cursor } pageInfo { ... }` shape for
+/// free.
+pub fn connection<'a, T, TypeLock>(
+    node: SelectionSet<'a, 'static, T, TypeLock>,
+) -> SelectionSet<'a, 'static, Connection<T>, TypeLock>
+where
+    T: 'a,
+    TypeLock: 'a,
+{
+    let edge: SelectionSet<'a, 'static, Edge<T>, ()> = selection_set::map2(
+        |node, cursor| Edge { cursor, node },
+        selection_set::field("node", vec![], node),
+        selection_set::field("cursor", vec![], selection_set::string()),
+    );
+
+    let page_info: SelectionSet<'a, 'static, PageInfo, ()> = selection_set::map4(
+        |has_next_page, has_previous_page, start_cursor, end_cursor| PageInfo {
+            has_next_page,
+            has_previous_page,
+            start_cursor,
+            end_cursor,
+        },
+        selection_set::field("hasNextPage", vec![], selection_set::boolean()),
+        selection_set::field("hasPreviousPage", vec![], selection_set::boolean()),
+        selection_set::field(
+            "startCursor",
+            vec![],
+            selection_set::option(selection_set::string()),
+        ),
+        selection_set::field(
+            "endCursor",
+            vec![],
+            selection_set::option(selection_set::string()),
+        ),
+    );
+
+    selection_set::map2(
+        |edges, page_info| Connection { edges, page_info },
+        selection_set::field("edges", vec![], selection_set::vec(edge)),
+        selection_set::field("pageInfo", vec![], page_info),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection_of(cursors: &[&str]) -> Connection<String> {
+        Connection {
+            edges: cursors
+                .iter()
+                .map(|cursor| Edge {
+                    cursor: cursor.to_string(),
+                    node: format!("node-{}", cursor),
+                })
+                .collect(),
+            page_info: PageInfo {
+                has_next_page: false,
+                has_previous_page: false,
+                start_cursor: cursors.first().map(|c| c.to_string()),
+                end_cursor: cursors.last().map(|c| c.to_string()),
+            },
+        }
+    }
+
+    #[test]
+    fn nodes_and_edges_stay_index_aligned() {
+        let connection = connection_of(&["a", "b", "c"]);
+
+        let nodes: Vec<_> = connection.nodes().collect();
+        let edges: Vec<_> = connection.edges().collect();
+
+        assert_eq!(nodes.len(), edges.len());
+        for (node, edge) in nodes.iter().zip(edges.iter()) {
+            assert_eq!(**node, edge.node);
+        }
+    }
+
+    #[test]
+    fn empty_edges_with_no_next_page_signals_the_end_of_pagination() {
+        let connection = connection_of(&[]);
+
+        assert_eq!(connection.nodes().count(), 0);
+        assert_eq!(connection.edges().count(), 0);
+        assert!(!connection.page_info.has_next_page);
+    }
+
+    #[test]
+    fn cursors_are_threaded_through_unchanged() {
+        let connection = connection_of(&["opaque-cursor-1", "opaque-cursor-2"]);
+
+        let cursors: Vec<_> = connection.edges().map(|edge| edge.cursor.as_str()).collect();
+        assert_eq!(cursors, vec!["opaque-cursor-1", "opaque-cursor-2"]);
+        assert_eq!(
+            connection.page_info.end_cursor.as_deref(),
+            Some("opaque-cursor-2")
+        );
+    }
+
+    fn field_names(fields: &[crate::field::Field]) -> Vec<String> {
+        fields
+            .iter()
+            .flat_map(|field| {
+                let mut names = vec![field.name.clone()];
+                names.extend(field_names(&field.fields));
+                names
+            })
+            .collect()
+    }
+
+    #[test]
+    fn connection_selects_the_relay_field_names() {
+        let node: SelectionSet<'_, 'static, String, ()> =
+            selection_set::field("name", vec![], selection_set::string());
+        let built = connection(node);
+
+        let names = field_names(&built.fields);
+
+        for expected in [
+            "edges",
+            "node",
+            "name",
+            "cursor",
+            "pageInfo",
+            "hasNextPage",
+            "hasPreviousPage",
+            "startCursor",
+            "endCursor",
+        ] {
+            assert!(
+                names.contains(&expected.to_string()),
+                "expected field `{}` in generated selection, got {:?}",
+                expected,
+                names
+            );
+        }
+    }
+
+    #[test]
+    fn connection_decodes_a_full_relay_response() {
+        let node: SelectionSet<'_, 'static, String, ()> =
+            selection_set::field("name", vec![], selection_set::string());
+        let built = connection(node);
+
+        let response = serde_json::json!({
+            "edges": [
+                {"node": {"name": "Luke"}, "cursor": "c1"},
+                {"node": {"name": "Leia"}, "cursor": "c2"},
+            ],
+            "pageInfo": {
+                "hasNextPage": true,
+                "hasPreviousPage": false,
+                "startCursor": "c1",
+                "endCursor": "c2",
+            }
+        });
+
+        let connection = built.decode(&response).unwrap();
+
+        assert_eq!(
+            connection.nodes().collect::<Vec<_>>(),
+            vec!["Luke", "Leia"]
+        );
+        assert_eq!(
+            connection
+                .edges()
+                .map(|edge| edge.cursor.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c1", "c2"]
+        );
+        assert!(connection.page_info.has_next_page);
+        assert_eq!(connection.page_info.end_cursor.as_deref(), Some("c2"));
+    }
+
+    #[test]
+    fn to_arguments_only_includes_fields_that_were_set() {
+        let args = ConnectionArguments {
+            first: Some(10),
+            after: Some("cursor1".to_string()),
+            last: None,
+            before: None,
+        };
+
+        let arguments = args.to_arguments();
+        let names: Vec<_> = arguments.iter().map(|argument| argument.name()).collect();
+
+        assert_eq!(names, vec!["first", "after"]);
+        assert_eq!(arguments[0].value(), &serde_json::json!(10));
+        assert_eq!(arguments[1].value(), &serde_json::json!("cursor1"));
+    }
+}
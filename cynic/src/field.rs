@@ -0,0 +1,37 @@
+use crate::Argument;
+
+/// A single field of a GraphQL selection set: its name, any arguments it was
+/// called with, and the (possibly empty) set of sub-fields selected on it.
+///
+/// This is the tree [`crate::selection_set::SelectionSet`] builds up as
+/// combinators are composed together - it's only `pub(crate)` since it's an
+/// implementation detail of `selection_set` and `query`, not something users
+/// build directly.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<Argument>,
+    pub(crate) fields: Vec<Field>,
+}
+
+impl Field {
+    pub(crate) fn leaf(name: impl Into<String>, arguments: Vec<Argument>) -> Self {
+        Field {
+            name: name.into(),
+            arguments,
+            fields: vec![],
+        }
+    }
+
+    pub(crate) fn nested(
+        name: impl Into<String>,
+        arguments: Vec<Argument>,
+        fields: Vec<Field>,
+    ) -> Self {
+        Field {
+            name: name.into(),
+            arguments,
+            fields,
+        }
+    }
+}
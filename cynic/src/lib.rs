@@ -162,12 +162,14 @@ mod query;
 mod result;
 mod scalar;
 
+pub mod connection;
 pub mod selection_set;
 pub mod utils;
 
 pub use json_decode::DecodeError;
 
 pub use argument::{Argument, SerializableArgument};
+pub use connection::{Connection, ConnectionArguments, Edge, PageInfo};
 pub use id::Id;
 pub use query::Query;
 pub use result::{GraphQLError, GraphQLResponse, GraphQLResult, PossiblyParsedData};
@@ -192,6 +194,17 @@ pub trait InlineFragments<'a>: Sized {
     fn fragments(
         arguments: Self::Arguments,
     ) -> Vec<(String, SelectionSet<'a, 'static, Self, Self::TypeLock>)>;
+
+    /// The variant to decode into when a server returns a `__typename` that
+    /// doesn't match any of the names in `fragments()`.
+    ///
+    /// Returning `None` (the default) means an unrecognised `__typename`
+    /// remains a hard decode error.  Implementing this lets a union or
+    /// interface grow new members on the server without breaking existing
+    /// clients that don't yet know about them.
+    fn fallback() -> Option<Self> {
+        None
+    }
 }
 
 impl<'a, T> QueryFragment<'a> for T
@@ -202,7 +215,12 @@ where
     type Arguments = <T as InlineFragments<'a>>::Arguments;
 
     fn fragment(arguments: Self::Arguments) -> Self::SelectionSet {
-        selection_set::inline_fragments(Self::fragments(arguments))
+        // `Self::fallback` (not `Self::fallback()`) is passed through as a
+        // function pointer so `inline_fragments` can call it itself whenever
+        // a response's `__typename` doesn't match any of `fragments()` -
+        // including more than once, if this selection set is decoded as part
+        // of a list.
+        selection_set::inline_fragments(Self::fragments(arguments), Self::fallback)
     }
 
     fn graphql_type() -> String {
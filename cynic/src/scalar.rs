@@ -0,0 +1,132 @@
+use json_decode::DecodeError;
+
+use crate::SerializeError;
+
+/// A trait for GraphQL scalars.
+///
+/// Scalars are the leaf types of a GraphQL schema - values like `String`,
+/// `Int` & `Boolean` as well as any custom scalars an API defines (`DateTime`,
+/// `ID` and so on).
+///
+/// You'll usually not want to implement this by hand.  Instead,
+/// `#[derive(cynic::Scalar)]` on a newtype struct that wraps some other type
+/// which already implements `serde::Serialize`/`serde::Deserialize` - the
+/// derive will delegate `decode`/`encode` to the inner type for you:
+///
+/// ```rust,ignore
+/// #[derive(cynic::Scalar)]
+/// #[cynic(graphql_type = "ID")]
+/// struct VideogameId(pub u64);
+/// ```
+///
+/// Nothing stops more than one Rust type from implementing `Scalar` for the
+/// same GraphQL scalar - `VideogameId`, `EntrantId` & `PlayerId` can all bind
+/// to GraphQL's `ID` as long as each is backed by a type with a compatible
+/// `serde` representation.
+///
+/// The derive also generates a `SerializableArgument` impl per scalar (rather
+/// than a single blanket impl over `T: Scalar`), so that other
+/// `SerializableArgument` impls - e.g. for `Option<T>`/`Vec<T>` in
+/// [`crate::argument`] - don't conflict with it.
+pub trait Scalar: Sized {
+    /// The name of the GraphQL scalar this type represents, e.g. `"ID"`.
+    fn graphql_type() -> String;
+
+    /// Decodes this scalar from the JSON value returned by a GraphQL server.
+    fn decode(value: &serde_json::Value) -> Result<Self, DecodeError>;
+
+    /// Encodes this scalar into a JSON value suitable for use as a query
+    /// argument.
+    fn encode(&self) -> Result<serde_json::Value, SerializeError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Stands in for what `#[derive(cynic::Scalar)]` generates for
+    // `struct VideogameId(pub u64);` - we can't invoke the derive itself here
+    // since it lives in a separate proc-macro crate, but the hand-written impl
+    // below exercises exactly the behaviour the derive is responsible for.
+    #[derive(Debug, PartialEq)]
+    struct VideogameId(u64);
+
+    impl Scalar for VideogameId {
+        fn graphql_type() -> String {
+            "ID".to_string()
+        }
+
+        fn decode(value: &serde_json::Value) -> Result<Self, DecodeError> {
+            serde_json::from_value::<u64>(value.clone())
+                .map(VideogameId)
+                .map_err(|e| DecodeError::Other(e.to_string()))
+        }
+
+        fn encode(&self) -> Result<serde_json::Value, SerializeError> {
+            serde_json::to_value(self.0).map_err(|e| Box::new(e) as SerializeError)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct EntrantId(u64);
+
+    impl Scalar for EntrantId {
+        fn graphql_type() -> String {
+            "ID".to_string()
+        }
+
+        fn decode(value: &serde_json::Value) -> Result<Self, DecodeError> {
+            serde_json::from_value::<u64>(value.clone())
+                .map(EntrantId)
+                .map_err(|e| DecodeError::Other(e.to_string()))
+        }
+
+        fn encode(&self) -> Result<serde_json::Value, SerializeError> {
+            serde_json::to_value(self.0).map_err(|e| Box::new(e) as SerializeError)
+        }
+    }
+
+    impl crate::SerializableArgument for VideogameId {
+        fn serialize(&self) -> Result<serde_json::Value, SerializeError> {
+            Scalar::encode(self)
+        }
+    }
+
+    #[test]
+    fn round_trips_through_encode_and_decode() {
+        let id = VideogameId(1234);
+
+        let encoded = id.encode().unwrap();
+        assert_eq!(encoded, serde_json::json!(1234));
+
+        let decoded = VideogameId::decode(&encoded).unwrap();
+        assert_eq!(decoded, id);
+    }
+
+    #[test]
+    fn decode_surfaces_a_decode_error_for_mismatched_json() {
+        let result = VideogameId::decode(&serde_json::json!("not a number"));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn is_usable_as_a_serializable_argument() {
+        use crate::SerializableArgument;
+
+        let id = VideogameId(1234);
+        assert_eq!(id.serialize().unwrap(), serde_json::json!(1234));
+    }
+
+    #[test]
+    fn distinct_scalars_can_bind_to_the_same_graphql_type() {
+        assert_eq!(VideogameId::graphql_type(), "ID");
+        assert_eq!(EntrantId::graphql_type(), "ID");
+
+        // Same GraphQL type, same underlying JSON shape, but not the same
+        // Rust type - so the values can't be confused for one another.
+        let videogame_id = VideogameId(42);
+        let entrant_id = EntrantId(42);
+        assert_eq!(videogame_id.encode().unwrap(), entrant_id.encode().unwrap());
+    }
+}
@@ -0,0 +1,332 @@
+//! The low-level building blocks `#[derive(cynic::QueryFragment)]` and
+//! `#[derive(cynic::InlineFragments)]` compile down to.
+//!
+//! A [`SelectionSet`] pairs the GraphQL fields to select with a decoder that
+//! turns the corresponding JSON response back into `DecodesTo`.  `TypeLock`
+//! ties the selection set back to the GraphQL type it was built against
+//! (usually a marker type from a generated `query_dsl` module) so a selection
+//! set can't accidentally be attached to a field of the wrong type.  Most
+//! users won't build these by hand.
+
+use std::marker::PhantomData;
+
+use json_decode::DecodeError;
+
+use crate::{field::Field, Argument};
+
+type BoxDecoder<'a, DecodesTo> =
+    Box<dyn Fn(&serde_json::Value) -> Result<DecodesTo, DecodeError> + 'a>;
+
+pub struct SelectionSet<'a, 'q, DecodesTo, TypeLock> {
+    pub(crate) fields: Vec<Field>,
+    decoder: BoxDecoder<'a, DecodesTo>,
+    phantom: PhantomData<(&'q (), fn() -> TypeLock)>,
+}
+
+impl<'a, 'q, DecodesTo, TypeLock> SelectionSet<'a, 'q, DecodesTo, TypeLock> {
+    /// Decodes a JSON response value using this selection set's decoder.
+    pub fn decode(&self, value: &serde_json::Value) -> Result<DecodesTo, DecodeError> {
+        (self.decoder)(value)
+    }
+}
+
+/// Selects a single field, nesting `inner`'s selection underneath it and
+/// decoding from the value found at that field in the response.
+///
+/// The returned `SelectionSet`'s `TypeLock` is a fresh, freely-inferred type
+/// parameter representing the *parent* object the field is selected on - it's
+/// unrelated to `inner`'s own `TypeLock`, which is how a single field of one
+/// GraphQL type can nest a selection built against another.
+pub fn field<'a, 'q, DecodesTo, TypeLock, InnerTypeLock>(
+    name: &str,
+    arguments: Vec<Argument>,
+    inner: SelectionSet<'a, 'q, DecodesTo, InnerTypeLock>,
+) -> SelectionSet<'a, 'q, DecodesTo, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    let field_name = name.to_string();
+    let decode_inner = inner.decoder;
+
+    SelectionSet {
+        fields: vec![Field::nested(name.to_string(), arguments, inner.fields)],
+        decoder: Box::new(move |value| {
+            let value = value.get(&field_name).ok_or_else(|| {
+                DecodeError::Other(format!("response was missing the `{}` field", field_name))
+            })?;
+            decode_inner(value)
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// A selection set that decodes a GraphQL `String`.
+pub fn string<'a, 'q>() -> SelectionSet<'a, 'q, String, ()> {
+    SelectionSet {
+        fields: vec![],
+        decoder: Box::new(|value| {
+            value
+                .as_str()
+                .map(ToString::to_string)
+                .ok_or_else(|| DecodeError::Other(format!("expected a string, found {}", value)))
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// A selection set that decodes a GraphQL `Boolean`.
+pub fn boolean<'a, 'q>() -> SelectionSet<'a, 'q, bool, ()> {
+    SelectionSet {
+        fields: vec![],
+        decoder: Box::new(|value| {
+            value
+                .as_bool()
+                .ok_or_else(|| DecodeError::Other(format!("expected a boolean, found {}", value)))
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// Wraps `inner` so a GraphQL `null` decodes to `None` rather than an error.
+pub fn option<'a, 'q, DecodesTo, TypeLock>(
+    inner: SelectionSet<'a, 'q, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, 'q, Option<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    let decode_inner = inner.decoder;
+
+    SelectionSet {
+        fields: inner.fields,
+        decoder: Box::new(move |value| {
+            if value.is_null() {
+                Ok(None)
+            } else {
+                decode_inner(value).map(Some)
+            }
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// Wraps `inner` so a GraphQL list decodes to a `Vec` of `inner`'s type.
+pub fn vec<'a, 'q, DecodesTo, TypeLock>(
+    inner: SelectionSet<'a, 'q, DecodesTo, TypeLock>,
+) -> SelectionSet<'a, 'q, Vec<DecodesTo>, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    let decode_inner = inner.decoder;
+
+    SelectionSet {
+        fields: inner.fields,
+        decoder: Box::new(move |value| {
+            value
+                .as_array()
+                .ok_or_else(|| DecodeError::Other(format!("expected a list, found {}", value)))?
+                .iter()
+                .map(|item| decode_inner(item))
+                .collect()
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// Maps the decoded value of a selection set through `f`, without changing
+/// the fields it selects.
+///
+/// The output `TypeLock` is a fresh, freely-inferred parameter unrelated to
+/// `set`'s own `TypeLock` - this is what lets [`inline_fragments`] combine
+/// variants that each have their own object's `TypeLock` into one `Vec` that
+/// shares a single union/interface `TypeLock`.
+pub fn map<'a, 'q, F, T1, DecodesTo, TypeLock, InputTypeLock>(
+    f: F,
+    set: SelectionSet<'a, 'q, T1, InputTypeLock>,
+) -> SelectionSet<'a, 'q, DecodesTo, TypeLock>
+where
+    F: Fn(T1) -> DecodesTo + 'a,
+{
+    let decode_inner = set.decoder;
+
+    SelectionSet {
+        fields: set.fields,
+        decoder: Box::new(move |value| decode_inner(value).map(&f)),
+        phantom: PhantomData,
+    }
+}
+
+/// Combines two selection sets - selecting both sets of fields and decoding
+/// both values - into one via `f`.
+pub fn map2<'a, 'q, F, T1, T2, DecodesTo, TypeLock>(
+    f: F,
+    set1: SelectionSet<'a, 'q, T1, TypeLock>,
+    set2: SelectionSet<'a, 'q, T2, TypeLock>,
+) -> SelectionSet<'a, 'q, DecodesTo, TypeLock>
+where
+    F: Fn(T1, T2) -> DecodesTo + 'a,
+{
+    let mut fields = set1.fields;
+    fields.extend(set2.fields);
+    let decode1 = set1.decoder;
+    let decode2 = set2.decoder;
+
+    SelectionSet {
+        fields,
+        decoder: Box::new(move |value| Ok(f(decode1(value)?, decode2(value)?))),
+        phantom: PhantomData,
+    }
+}
+
+/// Combines four selection sets into one via `f`, the same way [`map2`] does
+/// for two.
+pub fn map4<'a, 'q, F, T1, T2, T3, T4, DecodesTo, TypeLock>(
+    f: F,
+    set1: SelectionSet<'a, 'q, T1, TypeLock>,
+    set2: SelectionSet<'a, 'q, T2, TypeLock>,
+    set3: SelectionSet<'a, 'q, T3, TypeLock>,
+    set4: SelectionSet<'a, 'q, T4, TypeLock>,
+) -> SelectionSet<'a, 'q, DecodesTo, TypeLock>
+where
+    F: Fn(T1, T2, T3, T4) -> DecodesTo + 'a,
+{
+    let mut fields = set1.fields;
+    fields.extend(set2.fields);
+    fields.extend(set3.fields);
+    fields.extend(set4.fields);
+    let decode1 = set1.decoder;
+    let decode2 = set2.decoder;
+    let decode3 = set3.decoder;
+    let decode4 = set4.decoder;
+
+    SelectionSet {
+        fields,
+        decoder: Box::new(move |value| {
+            Ok(f(
+                decode1(value)?,
+                decode2(value)?,
+                decode3(value)?,
+                decode4(value)?,
+            ))
+        }),
+        phantom: PhantomData,
+    }
+}
+
+/// Builds the selection set for a GraphQL union/interface field: selects
+/// `__typename` plus each variant's inline fragment, and decodes by matching
+/// the response's `__typename` against `fragments`, falling back to
+/// `fallback()` (if it returns `Some`) when nothing matches.
+///
+/// `fallback` is a plain function pointer, rather than an `Option<DecodesTo>`
+/// computed up front, so the resulting `SelectionSet` can decode more than
+/// once (e.g. once per item in a list) without requiring `DecodesTo: Clone`.
+pub fn inline_fragments<'a, 'q, DecodesTo, TypeLock>(
+    fragments: Vec<(String, SelectionSet<'a, 'q, DecodesTo, TypeLock>)>,
+    fallback: fn() -> Option<DecodesTo>,
+) -> SelectionSet<'a, 'q, DecodesTo, TypeLock>
+where
+    DecodesTo: 'a,
+{
+    let mut fields = vec![Field::leaf("__typename", vec![])];
+    let mut decoders = Vec::with_capacity(fragments.len());
+
+    for (typename, selection) in fragments {
+        fields.push(Field::nested(
+            format!("... on {}", typename),
+            vec![],
+            selection.fields,
+        ));
+        decoders.push((typename, selection.decoder));
+    }
+
+    SelectionSet {
+        fields,
+        decoder: Box::new(move |value| {
+            let typename = value
+                .get("__typename")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    DecodeError::Other("response was missing the `__typename` field".to_string())
+                })?;
+
+            for (candidate, decode) in &decoders {
+                if candidate == typename {
+                    return decode(value);
+                }
+            }
+
+            fallback().ok_or_else(|| {
+                DecodeError::Other(format!(
+                    "unrecognised __typename `{}` and no fallback variant was provided",
+                    typename
+                ))
+            })
+        }),
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Character {
+        Human(String),
+        Droid(String),
+        Unknown,
+    }
+
+    fn human_fragment<'a, 'q>() -> SelectionSet<'a, 'q, Character, ()> {
+        map(Character::Human, field::<_, (), _>("name", vec![], string()))
+    }
+
+    fn droid_fragment<'a, 'q>() -> SelectionSet<'a, 'q, Character, ()> {
+        map(
+            Character::Droid,
+            field::<_, (), _>("primaryFunction", vec![], string()),
+        )
+    }
+
+    #[test]
+    fn decodes_the_variant_matching_typename() {
+        let selection_set = inline_fragments(
+            vec![
+                ("Human".to_string(), human_fragment()),
+                ("Droid".to_string(), droid_fragment()),
+            ],
+            || None,
+        );
+
+        let response = serde_json::json!({
+            "__typename": "Droid",
+            "primaryFunction": "Astromech",
+        });
+
+        assert_eq!(
+            selection_set.decode(&response).unwrap(),
+            Character::Droid("Astromech".to_string())
+        );
+    }
+
+    #[test]
+    fn falls_back_on_an_unrecognised_typename() {
+        let selection_set =
+            inline_fragments(vec![("Human".to_string(), human_fragment())], || {
+                Some(Character::Unknown)
+            });
+
+        let response = serde_json::json!({ "__typename": "Wookiee" });
+
+        assert_eq!(selection_set.decode(&response).unwrap(), Character::Unknown);
+    }
+
+    #[test]
+    fn errors_on_an_unrecognised_typename_with_no_fallback() {
+        let selection_set = inline_fragments(vec![("Human".to_string(), human_fragment())], || None);
+
+        let response = serde_json::json!({ "__typename": "Wookiee" });
+
+        assert!(selection_set.decode(&response).is_err());
+    }
+}